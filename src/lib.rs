@@ -1,4 +1,34 @@
-use rand::random;
+/* `std` is on by default (it's what pulls in `ThreadRng` for entropy-seeded
+ * construction). Disabling it (`--no-default-features`) builds this crate
+ * `no_std`, for embedded/DSP targets that supply their own seeded RNG via
+ * `Pink::from_seed` / `Pink::from_rng` instead of `Pink::new`.
+ *
+ * The ziggurat method's tail sampling needs `ln`/`exp`, which `core` alone
+ * doesn't provide, so `NoiseKind::Gaussian` is also `std`-only for now.
+ */
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "std")]
+mod ziggurat;
+
+#[cfg(feature = "std")]
+use rand::rngs::ThreadRng;
+use rand::rngs::SmallRng;
+use rand::{Rng, RngCore, SeedableRng};
+
+/* Selects the distribution `Noise::update` draws from. `Uniform` is the
+ * original [-1, 1] draw; `Gaussian` produces standard-normal samples via
+ * the ziggurat method, for spectrally-flat noise with a normal amplitude
+ * distribution. `Gaussian` needs `ln`/`exp` from `std`, so it's unavailable
+ * under `no_std`.
+ */
+#[derive(Copy, Clone, PartialEq, Eq, Default)]
+pub enum NoiseKind{
+    #[default]
+    Uniform,
+    #[cfg(feature = "std")]
+    Gaussian,
+}
 
 #[derive(Copy, Clone)]
 struct Noise{
@@ -11,10 +41,14 @@ impl Noise{
             value: 0.0,
         }
     }
-    pub fn update(&mut self){
-        self.value = (random::<f32>() * 2.0) - 1.0;
+    pub fn update<R: RngCore + ?Sized>(&mut self, rng: &mut R, kind: NoiseKind){
+        self.value = match kind{
+            NoiseKind::Uniform => (rng.gen::<f32>() * 2.0) - 1.0,
+            #[cfg(feature = "std")]
+            NoiseKind::Gaussian => ziggurat::sample(rng),
+        };
     }
-    
+
     pub fn value(&self) -> f32{
         self.value
     }
@@ -22,43 +56,95 @@ impl Noise{
 
 const GENERATORS: usize = 15;
 
-pub struct Pink{
-    noise: [Noise; GENERATORS], // updated based on trailing zeros
+/* `N` is the number of octave generators, which fixes both the low-frequency
+ * reach of the pink spectrum and the `rollover` period (2^(N-1)). `N = 15`
+ * matches the original fixed-size behaviour; callers wanting deeper
+ * low-frequency energy (at the cost of more CPU/memory) can pick a larger
+ * `N`, e.g. `Pink::<_, 20>::new()`. `N` must be in `1..=32`.
+ */
+pub struct Pink<R: RngCore, const N: usize = GENERATORS>{
+    noise: [Noise; N], // updated based on trailing zeros
     white: Noise, // Updated every iteration
     pink: f32, // Actual noise
-    
+
     counter: u32,
 
     generators: u32,
     rollover: u32,
+
+    rng: R,
+    kind: NoiseKind,
+}
+
+#[cfg(feature = "std")]
+impl<const N: usize> Pink<ThreadRng, N>{
+    /* Seeds from OS entropy via `rand::thread_rng()`. Use `from_seed` or
+     * `from_rng` when reproducible output is required. Not available under
+     * `no_std`, since there's no OS entropy source to draw from.
+     */
+    pub fn new() -> Pink<ThreadRng, N>{
+        Pink::from_rng(rand::thread_rng())
+    }
 }
 
-impl Pink{
-    const GENERATORS: u32 = GENERATORS as u32;
-    
-    pub fn new() -> Pink{
+impl<const N: usize> Pink<SmallRng, N>{
+    /* Deterministic construction from a 64-bit seed, for regression tests
+     * and anywhere reproducible pink noise is needed.
+     */
+    pub fn from_seed(seed: u64) -> Pink<SmallRng, N>{
+        Pink::from_rng(SmallRng::seed_from_u64(seed))
+    }
+}
+
+impl<R: RngCore, const N: usize> Pink<R, N>{
+    const GENERATORS: u32 = N as u32;
+
+    /* Builds a `Pink` driven by a caller-supplied RNG, e.g. `StepRng` for
+     * fully predictable values in tests, or a `no_std`-friendly PRNG on
+     * embedded targets that lack an OS entropy source.
+     *
+     * `N` must be in `1..=32`: `Pink` always needs at least one octave
+     * generator (`rollover = 2^(N-1)` underflows for `N = 0`), and the
+     * `u32` rollover can't represent a shift of 32 or more (`N = 0` would
+     * need `2^-1`; `N >= 33` would need `2^32` or more).
+     */
+    pub fn from_rng(rng: R) -> Pink<R, N>{
+        assert!((1..=32).contains(&N), "Pink requires N in 1..=32 octave generators");
+
         Pink{
-            noise:[Noise::new(); Self::GENERATORS as usize],
+            noise:[Noise::new(); N],
             white: Noise::new(),
             pink: 0.0,
             counter: 1,
             generators: Self::GENERATORS,
             rollover: 2u32.pow(Self::GENERATORS - 1),
+            rng,
+            kind: NoiseKind::default(),
         }
-    } 
+    }
+
+    /* Switches the white-noise source to standard-normal (Gaussian)
+     * samples instead of the default uniform [-1, 1] draws. Only available
+     * with the `std` feature; see `NoiseKind::Gaussian`.
+     */
+    #[cfg(feature = "std")]
+    pub fn gaussian(mut self) -> Pink<R, N>{
+        self.kind = NoiseKind::Gaussian;
+        self
+    }
 
     fn get_noise_index(&self) -> u32{
         assert!(self.counter > 0);
         assert!(self.counter <= self.rollover);
-        
+
         self.counter.trailing_zeros()
     }
 
     fn increment_counter(&mut self){
         assert!(self.counter > 0);
         assert!(self.counter <= self.rollover);
-        
-        self.counter = self.counter & (self.rollover - 1); 
+
+        self.counter = self.counter & (self.rollover - 1);
         self.counter = self.counter + 1;
     }
 
@@ -71,80 +157,194 @@ impl Pink{
         assert!( index < self.generators as usize );
 
         self.pink = self.pink - self.noise[index].value();
-        self.noise[index].update();
+        self.noise[index].update(&mut self.rng, self.kind);
+        self.pink = self.pink + self.noise[index].value();
+
+        self.pink = self.pink - self.white.value();
+        self.white.update(&mut self.rng, self.kind);
+        self.pink = self.pink + self.white.value();
+
+        self.increment_counter();
+
+        self.pink / (self.generators as f32 + 1.0)
+    }
+
+    /* Same Voss-McCartney step as `update`, but the white-noise draws come
+     * from the supplied `rng` instead of the generator stored in `self`.
+     * The state being advanced (`noise`, `white`, `counter`) is still
+     * entirely `self`'s, so this composes with rand's sampling APIs, e.g.
+     * `rng.sample(&mut PinkDistribution::new(pink))`, without requiring
+     * `Pink` to give up its own stored RNG.
+     */
+    pub fn sample<R2: RngCore + ?Sized>(&mut self, rng: &mut R2) -> f32{
+
+        let index = self.get_noise_index() as usize;
+        assert!( index < self.generators as usize );
+
+        self.pink = self.pink - self.noise[index].value();
+        self.noise[index].update(rng, self.kind);
         self.pink = self.pink + self.noise[index].value();
 
         self.pink = self.pink - self.white.value();
-        self.white.update();
-        self.pink = self.pink + self.white.value(); 
+        self.white.update(rng, self.kind);
+        self.pink = self.pink + self.white.value();
 
         self.increment_counter();
 
         self.pink / (self.generators as f32 + 1.0)
     }
+
+    /* Fills `out` one sample per slot via repeated `update()` calls. For
+     * real-time audio, requesting a whole render block at once avoids a
+     * per-sample call into the generator from the host's hot loop, and
+     * leaves room to vectorize the white-noise regeneration later without
+     * changing this signature.
+     */
+    pub fn fill(&mut self, out: &mut [f32]){
+        for slot in out.iter_mut(){
+            *slot = self.update();
+        }
+    }
+
+    /* Same as `fill`, but for a caller that wants a fixed-size block
+     * returned by value rather than writing into a borrowed buffer.
+     */
+    pub fn next_block<const BLK: usize>(&mut self) -> [f32; BLK]{
+        let mut block = [0.0f32; BLK];
+        self.fill(&mut block);
+        block
+    }
+}
+
+/* Wraps a `Pink` so it can be driven through `rand::distributions::Distribution`,
+ * e.g. `rng.sample(&pink_distribution)`. `Distribution::sample` takes `&self`,
+ * but advancing the Voss-McCartney state needs `&mut self`, so the generator
+ * is kept behind a `RefCell` to supply that interior mutability.
+ */
+pub struct PinkDistribution<R: RngCore, const N: usize = GENERATORS>{
+    inner: core::cell::RefCell<Pink<R, N>>,
+}
+
+impl<R: RngCore, const N: usize> PinkDistribution<R, N>{
+    pub fn new(pink: Pink<R, N>) -> PinkDistribution<R, N>{
+        PinkDistribution{
+            inner: core::cell::RefCell::new(pink),
+        }
+    }
+}
+
+impl<R: RngCore, const N: usize> rand::distributions::Distribution<f32> for PinkDistribution<R, N>{
+    fn sample<R2: RngCore + ?Sized>(&self, rng: &mut R2) -> f32{
+        self.inner.borrow_mut().sample(rng)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    
+    use rand::rngs::mock::StepRng;
+
     #[test]
     fn init() {
         let n = Noise::new();
 
         assert_eq!(n.value(), 0.0);
     }
-    
+
     #[test]
     fn update_noise() {
         let mut n = Noise::new();
+        let mut rng = StepRng::new(0, 1);
 
         assert_eq!(n.value(), 0.0);
 
-        n.update();
-        
+        n.update(&mut rng, NoiseKind::Uniform);
+
         assert_ne!(n.value(), 0.0);
     }
-    
+
     #[test]
     fn update_twice() {
         let mut n = Noise::new();
+        let mut rng = StepRng::new(0, 1);
 
         assert_eq!(n.value(), 0.0);
 
-        n.update();
-        
+        n.update(&mut rng, NoiseKind::Uniform);
+
         assert_ne!(n.value(), 0.0);
 
-        n.update();
+        n.update(&mut rng, NoiseKind::Uniform);
         assert_ne!(n.value(), 0.0);
     }
-    
+
     #[test]
-    fn initialisation() {
-        let p = Pink::new();
-        
+    #[cfg(feature = "std")]
+    fn update_noise_gaussian() {
+        let mut n = Noise::new();
+        let mut rng = SmallRng::seed_from_u64(42);
+
+        assert_eq!(n.value(), 0.0);
+
+        n.update(&mut rng, NoiseKind::Gaussian);
+
+        assert_ne!(n.value(), 0.0);
+    }
+
+    /* The ziggurat only has 256 layers and an exponential-tail fallback,
+     * so it can't reproduce a standard normal exactly; this checks the
+     * sample variance over a large draw lands close to the expected 1.0,
+     * catching the class of bug where the tail and the layer tables
+     * disagree on how much probability mass layer 0 carries.
+     */
+    #[test]
+    #[cfg(feature = "std")]
+    fn gaussian_sample_variance_is_close_to_one() {
+        let mut rng = SmallRng::seed_from_u64(42);
+        let samples = 1_000_000;
+
+        let mut sum = 0.0f64;
+        let mut sumsq = 0.0f64;
+        for _ in 0..samples {
+            let v = ziggurat::sample(&mut rng) as f64;
+            sum += v;
+            sumsq += v * v;
+        }
+
+        let mean = sum / samples as f64;
+        let variance = sumsq / samples as f64 - mean * mean;
+
+        assert!((variance - 1.0).abs() < 0.01, "variance was {variance}");
+    }
+
+    fn check_initialisation<const N: usize>() {
+        let p: Pink<SmallRng, N> = Pink::from_seed(0);
+
         assert_eq!(p.counter, 1);
-        assert_eq!(p.generators, Pink::GENERATORS);
+        assert_eq!(p.generators, Pink::<SmallRng, N>::GENERATORS);
         assert_eq!(p.pink, 0.0);
-        assert_eq!(p.rollover,16384);
+        assert_eq!(p.rollover, 2u32.pow(N as u32 - 1));
         assert_eq!(p.white.value(), 0.0);
 
-        for i in 0..Pink::GENERATORS{
+        for i in 0..Pink::<SmallRng, N>::GENERATORS{
             assert_eq!(p.noise[i as usize].value(), 0.0);
         }
-
     }
-    
+
     #[test]
-    fn update_voss() {
-        let mut p = Pink::new();
+    fn initialisation() {
+        check_initialisation::<15>();
+        check_initialisation::<8>();
+    }
+
+    fn check_update_voss<const N: usize>() {
+        let mut p: Pink<SmallRng, N> = Pink::from_seed(0);
         assert_eq!(p.counter, 1);
-        assert_eq!(p.generators, Pink::GENERATORS);
+        assert_eq!(p.generators, Pink::<SmallRng, N>::GENERATORS);
         assert_eq!(p.pink, 0.0);
-        assert_eq!(p.rollover,16384);
+        assert_eq!(p.rollover, 2u32.pow(N as u32 - 1));
         assert_eq!(p.white.value(), 0.0);
-       
+
         p.update();
         assert_ne!(p.pink, 0.0);
         assert_ne!(p.white.value(), 0.0);
@@ -152,100 +352,184 @@ mod tests {
     }
 
     #[test]
-    fn index_distribution() {
-        let mut p = Pink::new();
-        let mut count: [u32; Pink::GENERATORS as usize] = [0; Pink::GENERATORS as usize];
+    fn update_voss() {
+        check_update_voss::<15>();
+        check_update_voss::<8>();
+    }
+
+    #[test]
+    fn update_is_reproducible_from_seed() {
+        let mut a: Pink<SmallRng> = Pink::from_seed(1234);
+        let mut b: Pink<SmallRng> = Pink::from_seed(1234);
+
+        for _ in 0..64{
+            assert_eq!(a.update(), b.update());
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn gaussian_builder_affects_output() {
+        let mut uniform: Pink<StepRng> = Pink::from_rng(StepRng::new(0, 1));
+        let mut gaussian: Pink<StepRng> = Pink::from_rng(StepRng::new(0, 1)).gaussian();
+
+        assert_ne!(uniform.update(), gaussian.update());
+    }
+
+    #[test]
+    fn const_generic_generator_count() {
+        let p = Pink::<SmallRng, 8>::from_seed(0);
+
+        assert_eq!(p.generators, 8);
+        assert_eq!(p.rollover, 128);
+        assert_eq!(p.noise.len(), 8);
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_generators_is_rejected() {
+        let _ = Pink::<SmallRng, 0>::from_seed(0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn too_many_generators_is_rejected() {
+        let _ = Pink::<SmallRng, 33>::from_seed(0);
+    }
+
+    #[test]
+    fn const_generic_update_respects_rollover() {
+        let mut p = Pink::<SmallRng, 8>::from_seed(0);
+
+        for _ in 0..p.rollover{
+            p.update();
+        }
+
+        assert_eq!(p.counter, 1);
+    }
+
+    #[test]
+    fn sample_matches_update_with_same_rng_stream() {
+        let mut a: Pink<SmallRng> = Pink::from_seed(1234);
+        let mut b: Pink<StepRng> = Pink::from_rng(StepRng::new(0, 1));
+        let mut rng = StepRng::new(0, 1);
+
+        assert_eq!(a.sample(&mut rng), b.update());
+    }
+
+    #[test]
+    fn pink_distribution_samples() {
+        let pink: Pink<SmallRng> = Pink::from_seed(0);
+        let dist = PinkDistribution::new(pink);
+        let mut rng = StepRng::new(0, 1);
+
+        let first: f32 = rand::distributions::Distribution::sample(&dist, &mut rng);
+        let second: f32 = rand::distributions::Distribution::sample(&dist, &mut rng);
+
+        assert_ne!(first, second);
+    }
+
+    fn check_index_distribution<const N: usize>() {
+        let mut p: Pink<SmallRng, N> = Pink::from_seed(0);
+        let mut count: [u32; N] = [0; N];
 
         for _i in 0..p.rollover{
             let index = p.get_noise_index();
             count[index as usize] = count[index as usize] + 1;
             p.update();
         }
-        
-        for i in 0..Pink::GENERATORS - 1{
+
+        for i in 0..Pink::<SmallRng, N>::GENERATORS - 1{
             assert_eq!(count[i as usize], p.rollover >> (i + 1));
         }
-        
-        assert_eq!(count[(Pink::GENERATORS - 1) as usize], 1);
+
+        assert_eq!(count[(Pink::<SmallRng, N>::GENERATORS - 1) as usize], 1);
     }
-    
+
+    #[test]
+    fn index_distribution() {
+        check_index_distribution::<15>();
+        check_index_distribution::<8>();
+    }
+
     #[test]
     fn increment_counter() {
-        let mut p = Pink::new();
+        let mut p: Pink<SmallRng> = Pink::from_seed(0);
         assert_eq!(p.counter, 1);
 
         p.increment_counter();
         assert_eq!(p.counter, 2);
-        
+
         p.increment_counter();
         assert_eq!(p.counter, 3);
     }
-    
+
     #[test]
     fn increment_counter_rollover() {
-        let mut p = Pink::new();
+        let mut p: Pink<SmallRng> = Pink::from_seed(0);
         assert_eq!(p.counter, 1);
 
         p.counter = p.rollover - 1;
         p.increment_counter();
         assert_eq!(p.counter, p.rollover);
-        
+
         p.increment_counter();
         assert_eq!(p.counter, 1);
     }
 
     #[test]
-    fn trailing_zeros() {
-        let mut p = Pink::new();
-       
-        assert!(p.generators == 15);
-        assert!(p.counter == 1);
-        assert!(p.get_noise_index() == 0);
+    fn fill_writes_one_sample_per_slot() {
+        let mut p: Pink<SmallRng> = Pink::from_seed(0);
+        let mut out = [0.0f32; 8];
 
-        p.counter = 0b1u32;
-        assert!(p.get_noise_index() == 0);
-        
-        p.counter =0b10u32;
-        assert!(p.get_noise_index() == 1);
+        p.fill(&mut out);
 
-        p.counter =0b100u32;
-        assert!(p.get_noise_index() == 2);
-        
-        p.counter =0b1000u32;
-        assert!(p.get_noise_index() == 3);
+        assert!(out.iter().any(|&x| x != 0.0));
+    }
 
-        p.counter =0b10000u32;
-        assert!(p.get_noise_index() == 4);
+    #[test]
+    fn fill_matches_repeated_update() {
+        let mut a: Pink<SmallRng> = Pink::from_seed(1234);
+        let mut b: Pink<SmallRng> = Pink::from_seed(1234);
 
-        p.counter =0b100000u32;
-        assert!(p.get_noise_index() == 5);
+        let mut out = [0.0f32; 16];
+        a.fill(&mut out);
 
-        p.counter =0b1000000u32;
-        assert!(p.get_noise_index() == 6);
+        for expected in out{
+            assert_eq!(b.update(), expected);
+        }
+    }
 
-        p.counter =0b10000000u32;
-        assert!(p.get_noise_index() == 7);
+    #[test]
+    fn next_block_matches_fill() {
+        let mut a: Pink<SmallRng> = Pink::from_seed(5678);
+        let mut b: Pink<SmallRng> = Pink::from_seed(5678);
 
-        p.counter =0b100000000u32;
-        assert!(p.get_noise_index() == 8);
+        let block = a.next_block::<32>();
 
-        p.counter =0b1000000000u32;
-        assert!(p.get_noise_index() == 9);
+        let mut out = [0.0f32; 32];
+        b.fill(&mut out);
 
-        p.counter =0b10000000000u32;
-        assert!(p.get_noise_index() == 10);
+        assert_eq!(block, out);
+    }
 
-        p.counter =0b100000000000u32;
-        assert!(p.get_noise_index() == 11);
+    fn check_trailing_zeros<const N: usize>() {
+        let mut p: Pink<SmallRng, N> = Pink::from_seed(0);
 
-        p.counter = 0b1000000000000u32;
-        assert!(p.get_noise_index() == 12);
+        assert!(p.generators == N as u32);
+        assert!(p.counter == 1);
+        assert!(p.get_noise_index() == 0);
 
-        p.counter = 0b10000000000000u32;
-        assert!(p.get_noise_index() == 13);
+        for i in 0..N{
+            p.counter = 1u32 << i;
+            assert!(p.get_noise_index() == i as u32);
+        }
+    }
 
-        p.counter = 0b100000000000000u32;
-        assert!(p.get_noise_index() == 14);
+    #[test]
+    fn trailing_zeros() {
+        check_trailing_zeros::<15>();
+        check_trailing_zeros::<8>();
     }
 
 }
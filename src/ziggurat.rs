@@ -0,0 +1,185 @@
+/* Ziggurat-method sampler for standard-normal (Gaussian) white noise,
+ * used as an alternative to the uniform draws in `Noise::update`.
+ * https://www.jstatsoft.org/article/view/v005i08 (Marsaglia & Tsang, 2000)
+ *
+ * Tables and tail formula ported from the verified 256-layer normal
+ * ziggurat constants in `rand_distr` (ZIGNOR, Doornik 2005), truncated
+ * to f32 precision. `ZIGGURAT_X`/`ZIGGURAT_Y` hold the 257 layer
+ * boundaries and densities; `ZIGGURAT_R` is the boundary of layer 0
+ * used by the exponential-tail fallback below. Layer 0's box itself is
+ * scaled by `ZIGGURAT_X[0]`, which is larger than `ZIGGURAT_R` to
+ * account for the area the tail contributes.
+ */
+use rand::{Rng, RngCore};
+
+pub(super) const ZIGGURAT_R: f32 = 3.6541529_f32;
+pub(super) const ZIGGURAT_X: [f32; 257] = [
+    3.910758_f32, 3.6541529_f32, 3.4492784_f32, 3.3202448_f32,
+    3.224575_f32, 3.1478894_f32, 3.0835261_f32, 3.0278378_f32,
+    2.9786034_f32, 2.934367_f32, 2.8941212_f32, 2.8571386_f32,
+    2.8228774_f32, 2.7909212_f32, 2.760944_f32, 2.7326853_f32,
+    2.7059336_f32, 2.6805146_f32, 2.6562831_f32, 2.6331165_f32,
+    2.6109104_f32, 2.589576_f32, 2.5690355_f32, 2.5492215_f32,
+    2.5300753_f32, 2.5115445_f32, 2.493583_f32, 2.47615_f32,
+    2.4592085_f32, 2.4427254_f32, 2.426671_f32, 2.4110184_f32,
+    2.3957431_f32, 2.380823_f32, 2.3662372_f32, 2.3519673_f32,
+    2.3379962_f32, 2.324308_f32, 2.3108883_f32, 2.2977233_f32,
+    2.2848008_f32, 2.272109_f32, 2.259637_f32, 2.247375_f32,
+    2.2353134_f32, 2.2234433_f32, 2.2117567_f32, 2.2002456_f32,
+    2.1889029_f32, 2.1777215_f32, 2.166695_f32, 2.1558177_f32,
+    2.1450837_f32, 2.1344872_f32, 2.1240232_f32, 2.113687_f32,
+    2.1034741_f32, 2.0933797_f32, 2.0833998_f32, 2.0735302_f32,
+    2.0637674_f32, 2.054108_f32, 2.044548_f32, 2.0350842_f32,
+    2.025714_f32, 2.0164337_f32, 2.0072408_f32, 1.9981325_f32,
+    1.989106_f32, 1.9801589_f32, 1.9712887_f32, 1.9624931_f32,
+    1.9537697_f32, 1.9451165_f32, 1.9365314_f32, 1.9280124_f32,
+    1.9195573_f32, 1.9111645_f32, 1.9028322_f32, 1.8945585_f32,
+    1.8863418_f32, 1.8781805_f32, 1.870073_f32, 1.8620176_f32,
+    1.8540131_f32, 1.8460579_f32, 1.8381506_f32, 1.83029_f32,
+    1.8224746_f32, 1.8147032_f32, 1.8069746_f32, 1.7992876_f32,
+    1.791641_f32, 1.7840337_f32, 1.7764645_f32, 1.7689325_f32,
+    1.7614363_f32, 1.7539753_f32, 1.7465483_f32, 1.7391542_f32,
+    1.7317923_f32, 1.7244616_f32, 1.7171609_f32, 1.7098897_f32,
+    1.7026469_f32, 1.6954317_f32, 1.6882432_f32, 1.6810807_f32,
+    1.6739433_f32, 1.6668303_f32, 1.6597408_f32, 1.6526742_f32,
+    1.6456295_f32, 1.6386062_f32, 1.6316035_f32, 1.6246206_f32,
+    1.6176568_f32, 1.6107116_f32, 1.6037842_f32, 1.5968738_f32,
+    1.5899799_f32, 1.5831017_f32, 1.5762388_f32, 1.5693902_f32,
+    1.5625554_f32, 1.555734_f32, 1.548925_f32, 1.5421282_f32,
+    1.5353426_f32, 1.5285677_f32, 1.521803_f32, 1.5150478_f32,
+    1.5083016_f32, 1.5015637_f32, 1.4948335_f32, 1.4881105_f32,
+    1.481394_f32, 1.4746835_f32, 1.4679785_f32, 1.4612782_f32,
+    1.4545821_f32, 1.4478897_f32, 1.4412003_f32, 1.4345133_f32,
+    1.4278282_f32, 1.4211444_f32, 1.4144613_f32, 1.4077783_f32,
+    1.4010948_f32, 1.3944101_f32, 1.3877238_f32, 1.3810352_f32,
+    1.3743436_f32, 1.3676486_f32, 1.3609494_f32, 1.3542453_f32,
+    1.3475358_f32, 1.3408203_f32, 1.3340981_f32, 1.3273686_f32,
+    1.320631_f32, 1.3138846_f32, 1.307129_f32, 1.3003632_f32,
+    1.2935867_f32, 1.2867987_f32, 1.2799984_f32, 1.2731853_f32,
+    1.2663583_f32, 1.2595168_f32, 1.2526603_f32, 1.2457875_f32,
+    1.2388979_f32, 1.2319906_f32, 1.2250646_f32, 1.2181194_f32,
+    1.2111537_f32, 1.2041669_f32, 1.1971577_f32, 1.1901255_f32,
+    1.1830691_f32, 1.1759876_f32, 1.1688799_f32, 1.1617448_f32,
+    1.1545814_f32, 1.1473885_f32, 1.1401649_f32, 1.1329093_f32,
+    1.1256205_f32, 1.1182972_f32, 1.1109381_f32, 1.1035417_f32,
+    1.0961066_f32, 1.0886314_f32, 1.0811144_f32, 1.073554_f32,
+    1.0659487_f32, 1.0582964_f32, 1.0505956_f32, 1.0428443_f32,
+    1.0350405_f32, 1.027182_f32, 1.0192667_f32, 1.0112925_f32,
+    1.0032567_f32, 0.995157_f32, 0.98699075_f32, 0.9787552_f32,
+    0.9704473_f32, 0.96206415_f32, 0.95360243_f32, 0.9450587_f32,
+    0.9364293_f32, 0.92771053_f32, 0.91889817_f32, 0.9099879_f32,
+    0.9009752_f32, 0.89185506_f32, 0.88262224_f32, 0.87327105_f32,
+    0.8637955_f32, 0.85418916_f32, 0.84444493_f32, 0.8345553_f32,
+    0.8245122_f32, 0.8143067_f32, 0.8039291_f32, 0.79336905_f32,
+    0.782615_f32, 0.7716544_f32, 0.76047343_f32, 0.74905664_f32,
+    0.73738724_f32, 0.72544616_f32, 0.7132123_f32, 0.70066184_f32,
+    0.68776786_f32, 0.6744998_f32, 0.6608226_f32, 0.64669573_f32,
+    0.6320722_f32, 0.616897_f32, 0.6011046_f32, 0.5846168_f32,
+    0.5673382_f32, 0.5491517_f32, 0.52990973_f32, 0.5094233_f32,
+    0.48744395_f32, 0.46363434_f32, 0.4375184_f32, 0.40838912_f32,
+    0.37512133_f32, 0.33573753_f32, 0.2861746_f32, 0.2152419_f32,
+    0.0_f32,
+];
+pub(super) const ZIGGURAT_Y: [f32; 257] = [
+    0.00047746775_f32, 0.001260286_f32, 0.0026090727_f32, 0.0040379725_f32,
+    0.0055224034_f32, 0.0070508756_f32, 0.0086165825_f32, 0.010214971_f32,
+    0.011842757_f32, 0.01349745_f32, 0.015177088_f32, 0.016880084_f32,
+    0.01860512_f32, 0.020351097_f32, 0.022117063_f32, 0.023902204_f32,
+    0.025705803_f32, 0.027527235_f32, 0.02936594_f32, 0.031221418_f32,
+    0.033093218_f32, 0.03498094_f32, 0.036884215_f32, 0.038802706_f32,
+    0.04073611_f32, 0.042684145_f32, 0.044646554_f32, 0.046623096_f32,
+    0.048613552_f32, 0.050617725_f32, 0.05263542_f32, 0.05466646_f32,
+    0.05671069_f32, 0.058767952_f32, 0.060838107_f32, 0.062921025_f32,
+    0.065016575_f32, 0.06712466_f32, 0.069245145_f32, 0.07137795_f32,
+    0.07352297_f32, 0.07568013_f32, 0.077849336_f32, 0.080030516_f32,
+    0.082223594_f32, 0.08442851_f32, 0.08664519_f32, 0.088873595_f32,
+    0.09111365_f32, 0.09336531_f32, 0.09562854_f32, 0.09790328_f32,
+    0.1001895_f32, 0.10248716_f32, 0.10479622_f32, 0.10711667_f32,
+    0.109448455_f32, 0.111791566_f32, 0.11414598_f32, 0.116511665_f32,
+    0.11888862_f32, 0.1212768_f32, 0.123676226_f32, 0.12608688_f32,
+    0.12850872_f32, 0.13094178_f32, 0.13338603_f32, 0.13584147_f32,
+    0.13830812_f32, 0.14078595_f32, 0.14327498_f32, 0.14577521_f32,
+    0.14828664_f32, 0.15080929_f32, 0.15334316_f32, 0.15588826_f32,
+    0.15844461_f32, 0.16101222_f32, 0.1635911_f32, 0.16618128_f32,
+    0.16878277_f32, 0.1713956_f32, 0.17401977_f32, 0.17665532_f32,
+    0.17930228_f32, 0.18196066_f32, 0.1846305_f32, 0.18731181_f32,
+    0.19000465_f32, 0.19270904_f32, 0.195425_f32, 0.19815259_f32,
+    0.20089182_f32, 0.20364276_f32, 0.2064054_f32, 0.20917983_f32,
+    0.21196608_f32, 0.21476418_f32, 0.21757418_f32, 0.22039613_f32,
+    0.22323008_f32, 0.22607607_f32, 0.22893417_f32, 0.23180442_f32,
+    0.23468687_f32, 0.23758158_f32, 0.2404886_f32, 0.24340801_f32,
+    0.24633986_f32, 0.24928421_f32, 0.25224113_f32, 0.25521067_f32,
+    0.2581929_f32, 0.2611879_f32, 0.26419577_f32, 0.2672165_f32,
+    0.27025026_f32, 0.27329704_f32, 0.276357_f32, 0.27943015_f32,
+    0.2825166_f32, 0.28561643_f32, 0.28872973_f32, 0.2918566_f32,
+    0.2949971_f32, 0.2981513_f32, 0.3013194_f32, 0.30450138_f32,
+    0.30769742_f32, 0.31090757_f32, 0.31413195_f32, 0.31737062_f32,
+    0.3206238_f32, 0.3238915_f32, 0.32717383_f32, 0.33047098_f32,
+    0.33378303_f32, 0.33711007_f32, 0.34045225_f32, 0.34380972_f32,
+    0.34718257_f32, 0.35057095_f32, 0.35397497_f32, 0.3573948_f32,
+    0.3608306_f32, 0.36428246_f32, 0.36775056_f32, 0.37123504_f32,
+    0.3747361_f32, 0.37825382_f32, 0.3817884_f32, 0.38534003_f32,
+    0.38890886_f32, 0.39249507_f32, 0.39609882_f32, 0.3997203_f32,
+    0.40335974_f32, 0.4070173_f32, 0.41069314_f32, 0.41438752_f32,
+    0.41810066_f32, 0.4218327_f32, 0.42558393_f32, 0.42935455_f32,
+    0.43314478_f32, 0.43695486_f32, 0.44078502_f32, 0.44463557_f32,
+    0.4485067_f32, 0.45239872_f32, 0.45631185_f32, 0.4602464_f32,
+    0.4642027_f32, 0.46818095_f32, 0.47218153_f32, 0.47620472_f32,
+    0.48025087_f32, 0.48432028_f32, 0.48841327_f32, 0.49253026_f32,
+    0.49667156_f32, 0.50083756_f32, 0.50502867_f32, 0.5092452_f32,
+    0.5134877_f32, 0.5177565_f32, 0.52205205_f32, 0.5263749_f32,
+    0.5307253_f32, 0.5351039_f32, 0.53951126_f32, 0.54394776_f32,
+    0.548414_f32, 0.5529105_f32, 0.5574379_f32, 0.56199676_f32,
+    0.56658775_f32, 0.5712115_f32, 0.57586867_f32, 0.58055997_f32,
+    0.5852862_f32, 0.590048_f32, 0.59484625_f32, 0.59968174_f32,
+    0.60455537_f32, 0.60946804_f32, 0.6144207_f32, 0.6194144_f32,
+    0.62445_f32, 0.62952876_f32, 0.6346518_f32, 0.6398203_f32,
+    0.6450355_f32, 0.6502987_f32, 0.65561146_f32, 0.66097516_f32,
+    0.6663914_f32, 0.6718617_f32, 0.677388_f32, 0.68297213_f32,
+    0.6886161_f32, 0.69432193_f32, 0.7000919_f32, 0.7059285_f32,
+    0.71183425_f32, 0.71781194_f32, 0.72386456_f32, 0.72999525_f32,
+    0.7362076_f32, 0.7425053_f32, 0.7488924_f32, 0.7553735_f32,
+    0.76195335_f32, 0.7686373_f32, 0.7754313_f32, 0.78234184_f32,
+    0.78937614_f32, 0.79654235_f32, 0.80384946_f32, 0.81130785_f32,
+    0.8189292_f32, 0.82672685_f32, 0.8347163_f32, 0.84291565_f32,
+    0.85134625_f32, 0.86003363_f32, 0.86900866_f32, 0.87830967_f32,
+    0.88798463_f32, 0.8980959_f32, 0.90872645_f32, 0.9199915_f32,
+    0.93206006_f32, 0.94519895_f32, 0.9598791_f32, 0.9771017_f32,
+    1.0_f32,
+];
+
+/* Draws one standard-normal sample. The low 8 bits of a random `u32`
+ * pick a layer, the next bit is the sign, and the remaining 23 bits
+ * give a uniform fraction across the layer's width. The common case
+ * accepts immediately; the rare cases fall back to the exponential
+ * tail (layer 0) or a rejection test against the true density.
+ */
+pub(super) fn sample<R: RngCore + ?Sized>(rng: &mut R) -> f32 {
+    loop {
+        let bits = rng.next_u32();
+        let i = (bits & 0xff) as usize;
+        let sign = (bits >> 8) & 1 == 1;
+        let u = ((bits >> 9) as f32) / (1u32 << 23) as f32;
+
+        let x = u * ZIGGURAT_X[i];
+
+        if x < ZIGGURAT_X[i + 1] {
+            return if sign { -x } else { x };
+        }
+
+        if i == 0 {
+            let u1: f32 = rng.gen();
+            let u2: f32 = rng.gen();
+            let t = -u1.ln() / ZIGGURAT_R;
+            let x = ZIGGURAT_R + t;
+            if u2 < (-0.5 * t * t).exp() {
+                return if sign { -x } else { x };
+            }
+        } else {
+            let y = ZIGGURAT_Y[i] + (ZIGGURAT_Y[i + 1] - ZIGGURAT_Y[i]) * rng.gen::<f32>();
+            if y < (-0.5 * x * x).exp() {
+                return if sign { -x } else { x };
+            }
+        }
+    }
+}